@@ -1,3 +1,4 @@
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
@@ -16,7 +17,11 @@ use crate::exec::CommandSet;
 use crate::filesystem;
 #[cfg(unix)]
 use crate::filter::OwnerFilter;
-use crate::filter::SizeFilter;
+#[cfg(unix)]
+use crate::filter::XAttrFilter;
+use crate::filter::{FileFlagsFilter, SizeFilter, StreamFilter, TimeType};
+use crate::output::FormatTemplate;
+use regex::bytes::{Regex, RegexBuilder};
 
 // Type for options that don't have any values, but are used to negate
 // earlier options
@@ -83,7 +88,7 @@ impl clap::Args for Negations {
     dont_collapse_args_in_usage = true,
     after_help = "Note: `fd -h` prints a short and concise overview while `fd --help` gives all \
     details.",
-    group(ArgGroup::new("execs").args(&["exec", "exec-batch", "list-details"]).conflicts_with_all(&[
+    group(ArgGroup::new("execs").args(&["exec", "exec-batch", "list-details", "format"]).conflicts_with_all(&[
             "max-results", "has-results", "count"])),
 )]
 pub struct Opts {
@@ -122,6 +127,13 @@ pub struct Opts {
     /// Do not respect the global ignore file
     #[clap(long, action, hide = true)]
     pub no_global_ignore_file: bool,
+    /// Do not load default arguments from a config file
+    ///
+    /// Do not prepend default arguments read from the config file pointed to by
+    /// the 'FD_CONFIG_PATH' environment variable (or '~/.config/fd/config' if
+    /// unset) to the command line.
+    #[clap(long, action, hide_short_help = true)]
+    pub no_config: bool,
     /// Unrestricted search, alias for '--no-ignore --hidden'
     ///
     ///Perform an unrestricted search, including ignored and hidden files. This is
@@ -248,7 +260,7 @@ pub struct Opts {
     #[clap(long, hide_short_help = true, action, conflicts_with_all(&["size", "exact-depth"]))]
     pub prune: bool,
     /// Filter by type: file (f), directory (d), symlink (l),\nexecutable (x),
-    /// empty (e), socket (s), pipe (p))
+    /// empty (e), socket (s), pipe (p), block-device (b), char-device (c))
     ///
     /// Filter the search by type:
     ///
@@ -261,6 +273,9 @@ pub struct Opts {
     ///   'x' or 'executable':   executables
     ///   'e' or 'empty':        empty files or directories
     ///
+    ///   'b' or 'block-device': block devices
+    ///   'c' or 'char-device':  character devices
+    ///
     /// This option can be specified more than once to include multiple file types.
     /// Searching for '--type file --type symlink' will show both regular files as
     /// well as symlinks. Note that the 'executable' and 'empty' filters work differently:
@@ -287,7 +302,7 @@ pub struct Opts {
     ///       fd -te -td"
     #[clap(long = "type", short = 't', value_name = "filetype", hide_possible_values = true,
         arg_enum, action = ArgAction::Append, number_of_values = 1)]
-    pub filetype: Option<Vec<FileType>>,
+    filetype: Option<Vec<CliFileType>>,
     /// Filter by file extension
     ///
     /// (Additionally) filter search results by their file extension. Multiple
@@ -298,6 +313,23 @@ pub struct Opts {
     #[clap(long = "extension", short = 'e', value_name = "ext", action = ArgAction::Append, number_of_values = 1)]
     pub extensions: Option<Vec<String>>,
 
+    /// Print results according to a template
+    ///
+    /// Print results according to a template where '{}', '{/}', '{//}', '{.}' and
+    /// '{/.}' have the same meaning as with '--exec'. Additionally, metadata
+    /// placeholders are available:
+    ///
+    ///   '{size}':  size of the file
+    ///   '{mtime}': last modification time
+    ///   '{owner}': owning uid (unix only; not resolved to a user name)
+    ///   '{group}': owning gid (unix only; not resolved to a group name)
+    ///
+    /// Examples:
+    ///     --format '{mtime} {size} {}'
+    ///     --format '{/.}.bak'
+    #[clap(long, value_name = "fmt", hide_short_help = true)]
+    pub format: Option<String>,
+
     #[clap(flatten)]
     pub exec: Exec,
 
@@ -343,7 +375,21 @@ pub struct Opts {
         default_value = "auto",
         value_name = "when"
     )]
-    pub color: ColorWhen,
+    color: CliColorWhen,
+    /// Sort the results by the given key
+    ///
+    /// Sort results by the given key instead of streaming them in traversal order.
+    /// Sorting requires buffering all results in memory, so the same metadata
+    /// fetched for '--size'/'--changed-within' and friends is reused where possible
+    /// instead of calling 'stat' a second time.
+    #[clap(long, arg_enum, value_name = "key")]
+    sort: Option<CliSortKey>,
+    /// Reverse the sort order
+    ///
+    /// Reverse the order of the search results. Only has an effect in combination
+    /// with '--sort'.
+    #[clap(long, action, requires("sort"), hide_short_help = true)]
+    pub reverse: bool,
     /// Set number of threads
     ///
     /// Set number of threads to use for searching & executing (default: number
@@ -414,6 +460,45 @@ pub struct Opts {
         action
     )]
     pub changed_before: Option<String>,
+    /// Filter by file access time (newer than)
+    ///
+    /// Filter results based on the file access time. The argument can be provided
+    /// as a specific point in time (YYYY-MM-DD HH:MM:SS) or as a duration (10h, 1d, 35min).
+    /// If the time is not specified, it defaults to 00:00:00.
+    /// Examples:
+    ///     --accessed-within 2weeks
+    ///     --accessed-within '2018-10-27 10:00:00'
+    #[clap(long, value_name = "date|dur", number_of_values = 1, action, hide_short_help = true)]
+    pub accessed_within: Option<String>,
+    /// Filter by file access time (older than)
+    ///
+    /// Filter results based on the file access time. The argument can be provided
+    /// as a specific point in time (YYYY-MM-DD HH:MM:SS) or as a duration (10h, 1d, 35min).
+    /// Examples:
+    ///     --accessed-before '2018-10-27 10:00:00'
+    ///     --accessed-before 2weeks
+    #[clap(long, value_name = "date|dur", number_of_values = 1, action, hide_short_help = true)]
+    pub accessed_before: Option<String>,
+    /// Filter by file creation time (newer than)
+    ///
+    /// Filter results based on the file creation (birth) time, where the platform
+    /// and filesystem expose it. The argument can be provided as a specific point
+    /// in time (YYYY-MM-DD HH:MM:SS) or as a duration (10h, 1d, 35min).
+    /// Examples:
+    ///     --created-within 2weeks
+    ///     --created-within '2018-10-27 10:00:00'
+    #[clap(long, value_name = "date|dur", number_of_values = 1, action, hide_short_help = true)]
+    pub created_within: Option<String>,
+    /// Filter by file creation time (older than)
+    ///
+    /// Filter results based on the file creation (birth) time, where the platform
+    /// and filesystem expose it. The argument can be provided as a specific point
+    /// in time (YYYY-MM-DD HH:MM:SS) or as a duration (10h, 1d, 35min).
+    /// Examples:
+    ///     --created-before '2018-10-27 10:00:00'
+    ///     --created-before 2weeks
+    #[clap(long, value_name = "date|dur", number_of_values = 1, action, hide_short_help = true)]
+    pub created_before: Option<String>,
     /// Limit number of search results
     ///
     /// Limit the number of search results to 'count' and quit immediately.
@@ -468,6 +553,19 @@ pub struct Opts {
     /// pass '--' first, or it will be considered as a flag (fd -- '-foo').
     #[clap(value_parser, default_value = "")]
     pub pattern: String,
+    /// Additional search patterns that need to match
+    ///
+    /// Add additional patterns that need to match, in addition to the primary
+    /// pattern. A file is only considered a match if it satisfies the primary
+    /// pattern and every '--and' pattern. Each pattern honors the same
+    /// '--glob'/'--fixed-strings', case-sensitivity and '--full-path' settings
+    /// as the primary pattern.
+    ///
+    /// Examples:
+    ///     fd foo --and bar
+    ///     fd --glob '*.png' --and '5[0-9][0-9]x*'
+    #[clap(long, value_name = "pattern", action = ArgAction::Append, number_of_values = 1)]
+    pub and: Vec<String>,
     /// Set path separator when printing file paths
     /// Set the path separator to use when printing file paths. The default is
     /// the OS-specific separator ('/' on Unix, '\\' on Windows).
@@ -505,6 +603,45 @@ pub struct Opts {
     #[cfg(unix)]
     #[clap(long, short = 'o', value_parser = OwnerFilter::from_string, value_name = "user:group")]
     pub owner: Option<OwnerFilter>,
+    /// Filter by extended attribute
+    ///
+    /// Filter files by the presence or value of an extended attribute (unix only).
+    /// Format: 'name' (must be present), 'name=value' (exact match), or
+    /// 'name=~pattern' (regex match against the raw attribute bytes).
+    /// This option can be specified more than once; a file must satisfy all of them.
+    ///
+    /// Examples:
+    ///     --xattr user.comment
+    ///     --xattr 'user.comment=todo'
+    ///     --xattr 'user.comment=~(?i)todo'
+    #[cfg(unix)]
+    #[clap(long, value_parser = XAttrFilter::from_string, value_name = "name[=value]", action = ArgAction::Append, number_of_values = 1, hide_short_help = true)]
+    pub xattr: Vec<XAttrFilter>,
+    /// Filter by filesystem attribute flags
+    ///
+    /// Filter files by filesystem metadata flags such as hidden, system, archive,
+    /// read-only, or reparse-point/symlink status.
+    /// Format: a comma-separated list of flag names. Precede a flag with '!' to
+    /// require that it is absent instead of present.
+    ///
+    /// Examples:
+    ///     --file-flags hidden
+    ///     --file-flags hidden,system
+    ///     --file-flags '!archive'
+    #[clap(long, value_parser = FileFlagsFilter::from_string, value_name = "flags", hide_short_help = true)]
+    pub file_flags: Option<FileFlagsFilter>,
+    /// Filter by presence of an NTFS alternate data stream
+    ///
+    /// Select only files that carry an NTFS alternate data stream (ADS), e.g.
+    /// the 'Zone.Identifier' stream Windows uses to mark web downloads. If a
+    /// name is given, only a stream with that exact name counts. On non-NTFS
+    /// targets this option is accepted but never matches.
+    ///
+    /// Examples:
+    ///     --has-stream
+    ///     --has-stream=Zone.Identifier
+    #[clap(long, value_name = "name", hide_short_help = true, value_parser)]
+    pub has_stream: Option<Option<String>>,
     /// Do not descend into a different file system
     ///
     /// By default, fd will traverse the file system tree as far as other options
@@ -524,6 +661,34 @@ pub struct Opts {
 }
 
 impl Opts {
+    /// Parse the command line, honoring default arguments from a config file
+    /// unless '--no-config' was given as one of fd's own options.
+    ///
+    /// This mirrors how ripgrep honors 'RIPGREP_CONFIG_PATH': arguments read from
+    /// the config file are prepended to the real 'argv', so that anything the
+    /// user actually typed on the command line still overrides them.
+    fn parse_args() -> Self {
+        let args: Vec<OsString> = std::env::args_os().collect();
+
+        if no_config_requested(&args) {
+            return <Self as Parser>::parse_from(args);
+        }
+
+        match config_args() {
+            Ok(config_args) if !config_args.is_empty() => {
+                let combined = std::iter::once(args[0].clone())
+                    .chain(config_args.into_iter().map(OsString::from))
+                    .chain(args.into_iter().skip(1));
+                <Self as Parser>::parse_from(combined)
+            }
+            Ok(_) => <Self as Parser>::parse_from(args),
+            Err(err) => {
+                print_error(format!("Could not read fd config file: {}", err));
+                <Self as Parser>::parse_from(args)
+            }
+        }
+    }
+
     pub fn search_paths(&self) -> anyhow::Result<Vec<PathBuf>> {
         // would it make sense to concatenate these?
         let paths = if !self.path.is_empty() {
@@ -584,6 +749,79 @@ impl Opts {
         self.max_results.filter(|&m| m > 0).or_else(|| self.max_one_result.then(|| 1))
     }
 
+    /// All of the (clock, bound, date-or-duration) time predicates that were
+    /// requested on the command line, in the form the walker expects to apply
+    /// them uniformly. Creation-time predicates are included here even though
+    /// they will be skipped by the walker on filesystems that don't report a
+    /// birth time.
+    pub fn time_constraints(&self) -> Vec<(TimeType, TimeBound, &str)> {
+        let fields = [
+            (TimeType::Modified, &self.changed_within, &self.changed_before),
+            (TimeType::Accessed, &self.accessed_within, &self.accessed_before),
+            (TimeType::Created, &self.created_within, &self.created_before),
+        ];
+
+        fields
+            .into_iter()
+            .flat_map(|(field, after, before)| {
+                let after = after
+                    .as_deref()
+                    .map(|s| (field, TimeBound::After, s))
+                    .into_iter();
+                let before = before
+                    .as_deref()
+                    .map(|s| (field, TimeBound::Before, s))
+                    .into_iter();
+                after.chain(before)
+            })
+            .collect()
+    }
+
+    /// The stream filter requested via '--has-stream', if any. The bare flag
+    /// (no '=name') is represented as `Some(None)` by clap and is forwarded to
+    /// `StreamFilter::from_string` as an empty spec, matching the "has any
+    /// stream" case documented there.
+    pub fn stream_filter(&self) -> Option<StreamFilter> {
+        self.has_stream.as_ref().map(|name| {
+            StreamFilter::from_string(name.as_deref().unwrap_or(""))
+                .expect("StreamFilter::from_string never fails")
+        })
+    }
+
+    /// The `--and` patterns, compiled the same way as the primary pattern
+    /// (see the doc comment on `and`): honoring `--glob`/`--fixed-strings`,
+    /// smart-case/`--case-sensitive`/`--ignore-case`, and applied against
+    /// whatever the walker matches the primary pattern against (filename or,
+    /// with `--full-path`, the full path). Empty if `--and` was not given.
+    pub fn and_patterns(&self) -> anyhow::Result<Vec<Regex>> {
+        self.and
+            .iter()
+            .map(|pattern| self.compile_pattern(pattern))
+            .collect()
+    }
+
+    /// Compile a raw search pattern into a regex, applying the glob-to-regex
+    /// translation, literal-string escaping and smart-case rules fd uses for
+    /// the primary pattern. Shared with [`Self::and_patterns`] so that
+    /// `--and` patterns are matched exactly like the primary one.
+    fn compile_pattern(&self, pattern: &str) -> anyhow::Result<Regex> {
+        let translated = if self.glob {
+            glob_to_regex(pattern)
+        } else if self.fixed_strings {
+            regex::escape(pattern)
+        } else {
+            pattern.to_string()
+        };
+
+        let case_insensitive =
+            !self.case_sensitive && (self.ignore_case || !pattern_has_uppercase_char(pattern));
+
+        RegexBuilder::new(&translated)
+            .case_insensitive(case_insensitive)
+            .build()
+            .map_err(|e| anyhow::anyhow!("Invalid pattern '{}': {}", pattern, e))
+    }
+
     #[cfg(feature = "completions")]
     pub fn gen_completions(&self) -> anyhow::Result<Option<Shell>> {
         self.gen_completions
@@ -595,6 +833,88 @@ impl Opts {
     }
 }
 
+/// What fd should do with each search result, resolved once from the mutually
+/// exclusive '--exec'/'--exec-batch'/'--list-details'/'--format' arguments so
+/// that downstream code doesn't need to re-derive it from `Opts`.
+pub enum OutputMode {
+    /// Just print the path (the default).
+    Print,
+    Exec(CommandSet),
+    ExecBatch(CommandSet),
+    ListDetails,
+    Format(FormatTemplate),
+}
+
+/// The fully-resolved, typed configuration for a single run of fd.
+///
+/// This is the sole output of [`parse`]: it owns the raw, clap-generated
+/// `Opts` plus whatever has already been validated and normalized from it
+/// (currently [`OutputMode`] and the `--type`/`--color`/`--sort` selections).
+/// `Opts` still backs the rest of `Config` via `Deref` for convenience, but
+/// every field whose clap-parsing type derives `ArgEnum` is re-exposed here
+/// as a plain, non-clap type instead, so downstream code (the walker, exec,
+/// output formatting) never has to name a clap type and an alternative
+/// lightweight parser could be dropped in later without touching it.
+pub struct Config {
+    opts: Opts,
+    pub output_mode: OutputMode,
+    pub filetype: Option<Vec<FileType>>,
+    pub color: ColorWhen,
+    sort: Option<SortKey>,
+}
+
+impl Config {
+    fn new(mut opts: Opts) -> Self {
+        let output_mode = if let Some(command) = opts.exec.command.take() {
+            match command {
+                ExecCommand::Exec(c) => OutputMode::Exec(c),
+                ExecCommand::ExecBatch(c) => OutputMode::ExecBatch(c),
+            }
+        } else if opts.list_details {
+            OutputMode::ListDetails
+        } else if let Some(format) = opts.format.as_deref() {
+            OutputMode::Format(FormatTemplate::parse(format))
+        } else {
+            OutputMode::Print
+        };
+
+        let filetype = opts
+            .filetype
+            .take()
+            .map(|types| types.into_iter().map(FileType::from).collect());
+        let color = ColorWhen::from(opts.color);
+        let sort = opts.sort.map(SortKey::from);
+
+        Config {
+            opts,
+            output_mode,
+            filetype,
+            color,
+            sort,
+        }
+    }
+
+    /// The sort key and direction to use, if the user requested sorted output.
+    pub fn sort_by(&self) -> Option<(SortKey, bool)> {
+        self.sort.map(|key| (key, self.reverse))
+    }
+}
+
+impl std::ops::Deref for Config {
+    type Target = Opts;
+
+    fn deref(&self) -> &Opts {
+        &self.opts
+    }
+}
+
+/// Parse the command line (including any config-file defaults) into a fully
+/// resolved [`Config`]. This is the only place that should touch clap's
+/// `ArgMatches`/`Opts` directly.
+pub fn parse() -> Config {
+    Config::new(Opts::parse_args())
+}
+
 // TODO: windows?
 #[cfg(feature = "completions")]
 fn guess_shell() -> anyhow::Result<Shell> {
@@ -608,8 +928,12 @@ fn guess_shell() -> anyhow::Result<Shell> {
         .map_err(|_| anyhow!("Unknown shell {}", shell))
 }
 
+/// Clap-facing mirror of [`FileType`], kept private to this module. This is
+/// the type clap actually parses `--type` into (hence the `ArgEnum` derive
+/// and aliases); [`Config::new`] immediately converts it to the plain
+/// [`FileType`] that the rest of the crate works with.
 #[derive(Copy, Clone, PartialEq, Eq, ArgEnum)]
-pub enum FileType {
+enum CliFileType {
     #[clap(alias = "f")]
     File,
     #[clap(alias = "d")]
@@ -624,10 +948,136 @@ pub enum FileType {
     Socket,
     #[clap(alias = "p")]
     Pipe,
+    #[clap(alias = "b")]
+    BlockDevice,
+    #[clap(alias = "c")]
+    CharDevice,
+}
+
+impl From<CliFileType> for FileType {
+    fn from(cli: CliFileType) -> Self {
+        match cli {
+            CliFileType::File => FileType::File,
+            CliFileType::Directory => FileType::Directory,
+            CliFileType::Symlink => FileType::Symlink,
+            CliFileType::Executable => FileType::Executable,
+            CliFileType::Empty => FileType::Empty,
+            CliFileType::Socket => FileType::Socket,
+            CliFileType::Pipe => FileType::Pipe,
+            CliFileType::BlockDevice => FileType::BlockDevice,
+            CliFileType::CharDevice => FileType::CharDevice,
+        }
+    }
+}
+
+/// A file type to filter search results by, as resolved from `--type`.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum FileType {
+    File,
+    Directory,
+    Symlink,
+    Executable,
+    Empty,
+    Socket,
+    Pipe,
+    BlockDevice,
+    CharDevice,
+}
+
+impl FileType {
+    /// Test whether `metadata` matches this file type. 'executable' and
+    /// 'empty' aren't handled here since they need more than metadata (resp.
+    /// the entry's permission bits and a directory listing) and are resolved
+    /// by the walker instead.
+    #[cfg(unix)]
+    pub fn matches_metadata(&self, metadata: &std::fs::Metadata) -> bool {
+        use std::os::unix::fs::FileTypeExt;
+
+        let file_type = metadata.file_type();
+        match self {
+            FileType::File => file_type.is_file(),
+            FileType::Directory => file_type.is_dir(),
+            FileType::Symlink => file_type.is_symlink(),
+            FileType::Socket => file_type.is_socket(),
+            FileType::Pipe => file_type.is_fifo(),
+            FileType::BlockDevice => file_type.is_block_device(),
+            FileType::CharDevice => file_type.is_char_device(),
+            FileType::Executable | FileType::Empty => false,
+        }
+    }
+
+    /// Windows has no notion of block/char device nodes (or unix sockets and
+    /// FIFOs), so those variants simply never match there.
+    #[cfg(windows)]
+    pub fn matches_metadata(&self, metadata: &std::fs::Metadata) -> bool {
+        match self {
+            FileType::File => metadata.is_file(),
+            FileType::Directory => metadata.is_dir(),
+            FileType::Symlink => metadata.file_type().is_symlink(),
+            FileType::Socket
+            | FileType::Pipe
+            | FileType::BlockDevice
+            | FileType::CharDevice
+            | FileType::Executable
+            | FileType::Empty => false,
+        }
+    }
 }
 
+/// Clap-facing mirror of [`SortKey`]; see [`CliFileType`] for why this split
+/// exists.
 #[derive(Copy, Clone, PartialEq, Eq, Debug, ArgEnum)]
-pub enum ColorWhen {
+enum CliSortKey {
+    /// sort by the full path
+    Path,
+    /// sort by file name only
+    Name,
+    /// sort by file size
+    Size,
+    /// sort by modification time
+    Mtime,
+    /// sort by access time
+    Atime,
+    /// sort by file extension
+    Extension,
+}
+
+impl From<CliSortKey> for SortKey {
+    fn from(cli: CliSortKey) -> Self {
+        match cli {
+            CliSortKey::Path => SortKey::Path,
+            CliSortKey::Name => SortKey::Name,
+            CliSortKey::Size => SortKey::Size,
+            CliSortKey::Mtime => SortKey::Mtime,
+            CliSortKey::Atime => SortKey::Atime,
+            CliSortKey::Extension => SortKey::Extension,
+        }
+    }
+}
+
+/// The key to sort search results by, as resolved from `--sort`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SortKey {
+    Path,
+    Name,
+    Size,
+    Mtime,
+    Atime,
+    Extension,
+}
+
+/// Whether a time filter requires the timestamp to be newer or older than the
+/// reference point.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TimeBound {
+    After,
+    Before,
+}
+
+/// Clap-facing mirror of [`ColorWhen`]; see [`CliFileType`] for why this
+/// split exists.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, ArgEnum)]
+enum CliColorWhen {
     /// show colors if the output goes to an interactive console (default)
     Auto,
     /// always use colorized output
@@ -636,10 +1086,34 @@ pub enum ColorWhen {
     Never,
 }
 
+impl From<CliColorWhen> for ColorWhen {
+    fn from(cli: CliColorWhen) -> Self {
+        match cli {
+            CliColorWhen::Auto => ColorWhen::Auto,
+            CliColorWhen::Always => ColorWhen::Always,
+            CliColorWhen::Never => ColorWhen::Never,
+        }
+    }
+}
+
+/// Whether to use colored output, as resolved from `--color`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ColorWhen {
+    Auto,
+    Always,
+    Never,
+}
+
+/// Which of '--exec'/'--exec-batch' was given, if either.
+pub enum ExecCommand {
+    Exec(CommandSet),
+    ExecBatch(CommandSet),
+}
+
 // there isn't a derive api for getting grouped values yet,
 // so we have to use hand-rolled parsing for exec and exec-batch
 pub struct Exec {
-    pub command: Option<CommandSet>,
+    pub command: Option<ExecCommand>,
 }
 
 impl clap::FromArgMatches for Exec {
@@ -647,10 +1121,12 @@ impl clap::FromArgMatches for Exec {
         let command = matches
             .grouped_values_of("exec")
             .map(CommandSet::new)
+            .map(|r| r.map(ExecCommand::Exec))
             .or_else(|| {
                 matches
                     .grouped_values_of("exec-batch")
                     .map(CommandSet::new_batch)
+                    .map(|r| r.map(ExecCommand::ExecBatch))
             })
             .transpose()
             .map_err(|e| clap::Error::raw(ErrorKind::InvalidValue, e))?;
@@ -735,6 +1211,128 @@ fn parse_millis(arg: &str) -> Result<Duration, std::num::ParseIntError> {
     Ok(Duration::from_millis(arg.parse()?))
 }
 
+/// Whether a pattern contains an uppercase character, used to decide smart
+/// case (a pattern with an uppercase letter makes the search case-sensitive).
+fn pattern_has_uppercase_char(pattern: &str) -> bool {
+    pattern.chars().any(|c| c.is_uppercase())
+}
+
+/// Translate a glob pattern (`*`, `**`, `?`, `[...]`/`[!...]`) into an
+/// equivalent, fully-anchored regex. `*` does not cross a path separator;
+/// `**` does, matching any number of path components.
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("(?s)^");
+    let mut chars = glob.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => {
+                if chars.peek() == Some(&'*') {
+                    chars.next();
+                    regex.push_str(".*");
+                } else {
+                    regex.push_str("[^/]*");
+                }
+            }
+            '?' => regex.push_str("[^/]"),
+            '[' => {
+                regex.push('[');
+                if chars.peek() == Some(&'!') {
+                    chars.next();
+                    regex.push('^');
+                }
+                for c in chars.by_ref() {
+                    regex.push(c);
+                    if c == ']' {
+                        break;
+                    }
+                }
+            }
+            '.' | '+' | '(' | ')' | '|' | '^' | '$' | '\\' | '{' | '}' => {
+                regex.push('\\');
+                regex.push(c);
+            }
+            _ => regex.push(c),
+        }
+    }
+
+    regex.push('$');
+    regex
+}
+
+/// The arguments at and beyond any of these stop being fd's own options:
+/// '--' is the usual positional separator, and '--exec'/'-x'/'--exec-batch'/
+/// '-X' swallow everything up to their ';' terminator as the command to run.
+const ARGV_BOUNDARIES: &[&str] = &["--", "--exec", "-x", "--exec-batch", "-X"];
+
+/// Whether '--no-config' was given as one of fd's own options, i.e. ahead of
+/// any [`ARGV_BOUNDARIES`]. A literal '--no-config' used as a search pattern
+/// (after '--') or as an argument to the command given to '--exec' must not
+/// disable config loading.
+fn no_config_requested(args: &[OsString]) -> bool {
+    let boundary = args
+        .iter()
+        .position(|a| ARGV_BOUNDARIES.iter().any(|b| a == b))
+        .unwrap_or(args.len());
+    args[..boundary].iter().any(|a| a == "--no-config")
+}
+
+/// The path to the fd config file, taken from 'FD_CONFIG_PATH' if set, or
+/// '~/.config/fd/config' (using [`home_dir`] to find '~') otherwise.
+fn config_file_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("FD_CONFIG_PATH") {
+        return Some(PathBuf::from(path));
+    }
+
+    let home = home_dir()?;
+    Some(home.join(".config").join("fd").join("config"))
+}
+
+/// The current user's home directory. Reads '$HOME' on all platforms, since
+/// it's also respected by Windows-native shells such as PowerShell, falling
+/// back to '%USERPROFILE%', and then '%HOMEDRIVE%%HOMEPATH%', on Windows,
+/// where '$HOME' isn't always set.
+fn home_dir() -> Option<PathBuf> {
+    if let Some(home) = std::env::var_os("HOME") {
+        return Some(PathBuf::from(home));
+    }
+
+    #[cfg(windows)]
+    {
+        if let Some(profile) = std::env::var_os("USERPROFILE") {
+            return Some(PathBuf::from(profile));
+        }
+        let mut home = PathBuf::from(std::env::var_os("HOMEDRIVE")?);
+        home.push(std::env::var_os("HOMEPATH")?);
+        return Some(home);
+    }
+
+    #[cfg(not(windows))]
+    None
+}
+
+/// Read whitespace-separated default arguments from the config file, skipping
+/// blank lines and lines starting with '#'. Returns an empty vector (not an
+/// error) if no config file is present.
+fn config_args() -> anyhow::Result<Vec<String>> {
+    let Some(path) = config_file_path() else {
+        return Ok(Vec::new());
+    };
+
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+
+    let contents = std::fs::read_to_string(&path)?;
+    Ok(contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .flat_map(str::split_whitespace)
+        .map(String::from)
+        .collect())
+}
+
 fn ensure_current_directory_exists(current_directory: &Path) -> anyhow::Result<()> {
     if filesystem::is_existing_directory(current_directory) {
         Ok(())
@@ -745,3 +1343,103 @@ fn ensure_current_directory_exists(current_directory: &Path) -> anyhow::Result<(
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_and_char_device_aliases_parse_to_their_file_type() {
+        assert!(matches!(
+            CliFileType::from_str("b", true).map(FileType::from),
+            Ok(FileType::BlockDevice)
+        ));
+        assert!(matches!(
+            CliFileType::from_str("char-device", true).map(FileType::from),
+            Ok(FileType::CharDevice)
+        ));
+    }
+
+    #[test]
+    fn block_and_char_device_do_not_match_a_directory() {
+        let metadata = std::fs::metadata(".").unwrap();
+        assert!(!FileType::BlockDevice.matches_metadata(&metadata));
+        assert!(!FileType::CharDevice.matches_metadata(&metadata));
+        assert!(FileType::Directory.matches_metadata(&metadata));
+    }
+
+    // Both cases share one test (rather than running as separate #[test]s) so
+    // that concurrently-run tests never see each other's FD_CONFIG_PATH.
+    #[test]
+    fn config_args_reads_and_tokenizes_the_config_file() {
+        use crate::filter::test_support::TempDir;
+
+        let dir = TempDir::new("config");
+        let config_path = dir.join("config");
+        std::fs::write(
+            &config_path,
+            "# a comment\n--hidden --no-ignore\n\n  -tf  -e rs  \n",
+        )
+        .unwrap();
+
+        std::env::set_var("FD_CONFIG_PATH", &config_path);
+        let args = config_args().unwrap();
+        assert_eq!(args, vec!["--hidden", "--no-ignore", "-tf", "-e", "rs"]);
+
+        std::fs::remove_file(&config_path).unwrap();
+        let args = config_args().unwrap();
+        assert!(args.is_empty());
+
+        std::env::remove_var("FD_CONFIG_PATH");
+    }
+
+    #[test]
+    fn glob_star_does_not_cross_path_separator_but_double_star_does() {
+        let re = Regex::new(&glob_to_regex("*.png")).unwrap();
+        assert!(re.is_match(b"photo.png"));
+        assert!(!re.is_match(b"dir/photo.png"));
+
+        let re = Regex::new(&glob_to_regex("**/*.png")).unwrap();
+        assert!(re.is_match(b"a/b/photo.png"));
+    }
+
+    #[test]
+    fn glob_character_classes_translate_to_regex_classes() {
+        let re = Regex::new(&glob_to_regex("img[0-9].png")).unwrap();
+        assert!(re.is_match(b"img5.png"));
+        assert!(!re.is_match(b"imgx.png"));
+
+        let re = Regex::new(&glob_to_regex("img[!0-9].png")).unwrap();
+        assert!(re.is_match(b"imgx.png"));
+        assert!(!re.is_match(b"img5.png"));
+    }
+
+    #[test]
+    fn pattern_has_uppercase_char_detects_smart_case_trigger() {
+        assert!(!pattern_has_uppercase_char("foo.rs"));
+        assert!(pattern_has_uppercase_char("Foo.rs"));
+    }
+
+    fn os_strings(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn no_config_requested_before_separator_disables_config() {
+        assert!(no_config_requested(&os_strings(&[
+            "fd",
+            "--no-config",
+            "foo"
+        ])));
+    }
+
+    #[test]
+    fn no_config_requested_ignores_positionals_after_separator() {
+        assert!(!no_config_requested(&os_strings(&[
+            "fd", "foo", "--", "--no-config"
+        ])));
+        assert!(!no_config_requested(&os_strings(&[
+            "fd", "-x", "cmd", "--no-config", ";"
+        ])));
+    }
+}
+
@@ -0,0 +1,161 @@
+use std::fs::Metadata;
+
+use anyhow::{bail, Result};
+
+#[cfg(windows)]
+use std::os::windows::fs::MetadataExt;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+
+const HIDDEN: u8 = 0b0000_0001;
+const SYSTEM: u8 = 0b0000_0010;
+const ARCHIVE: u8 = 0b0000_0100;
+const READONLY: u8 = 0b0000_1000;
+const REPARSE_POINT: u8 = 0b0001_0000;
+
+fn flag_from_name(name: &str) -> Result<u8> {
+    match name {
+        "hidden" => Ok(HIDDEN),
+        "system" => Ok(SYSTEM),
+        "archive" => Ok(ARCHIVE),
+        "readonly" | "read-only" => Ok(READONLY),
+        "reparse-point" | "symlink" => Ok(REPARSE_POINT),
+        _ => bail!("Unknown file flag: '{}'", name),
+    }
+}
+
+/// Filters search results by filesystem metadata flags such as hidden, system,
+/// archive, read-only, or reparse-point status.
+///
+/// Parsed from a comma-separated spec like `hidden,system,!archive`, where a
+/// leading `!` negates a required flag (the flag must be absent rather than
+/// present).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileFlagsFilter {
+    required_present: u8,
+    required_absent: u8,
+}
+
+impl FileFlagsFilter {
+    pub fn from_string(input: &str) -> Result<Self> {
+        let mut required_present = 0u8;
+        let mut required_absent = 0u8;
+
+        for part in input.split(',') {
+            let part = part.trim();
+            if part.is_empty() {
+                continue;
+            }
+
+            if let Some(name) = part.strip_prefix('!') {
+                required_absent |= flag_from_name(name)?;
+            } else {
+                required_present |= flag_from_name(part)?;
+            }
+        }
+
+        Ok(FileFlagsFilter {
+            required_present,
+            required_absent,
+        })
+    }
+
+    #[cfg(windows)]
+    fn actual_flags(metadata: &Metadata) -> u8 {
+        let attrs = metadata.file_attributes();
+        let mut flags = 0u8;
+        if attrs & 0x2 != 0 {
+            flags |= HIDDEN; // FILE_ATTRIBUTE_HIDDEN
+        }
+        if attrs & 0x4 != 0 {
+            flags |= SYSTEM; // FILE_ATTRIBUTE_SYSTEM
+        }
+        if attrs & 0x20 != 0 {
+            flags |= ARCHIVE; // FILE_ATTRIBUTE_ARCHIVE
+        }
+        if attrs & 0x1 != 0 {
+            flags |= READONLY; // FILE_ATTRIBUTE_READONLY
+        }
+        if attrs & 0x400 != 0 {
+            flags |= REPARSE_POINT; // FILE_ATTRIBUTE_REPARSE_POINT
+        }
+        flags
+    }
+
+    // Only the portable subset is meaningful on unix: hidden = dotfile (handled
+    // by the caller, which already knows the file name), read-only = no write
+    // permission bits set, reparse-point/symlink = the entry is a symlink.
+    // System/archive don't exist here.
+    #[cfg(unix)]
+    fn actual_flags(metadata: &Metadata) -> u8 {
+        let mut flags = 0u8;
+        if metadata.permissions().mode() & 0o222 == 0 {
+            flags |= READONLY;
+        }
+        if metadata.file_type().is_symlink() {
+            flags |= REPARSE_POINT;
+        }
+        flags
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    fn actual_flags(_metadata: &Metadata) -> u8 {
+        0
+    }
+
+    /// Evaluate the filter against a search result's metadata. `is_hidden`
+    /// carries the dotfile check the walker already performs, since that's the
+    /// only portable definition of "hidden" on unix.
+    pub fn applies(&self, metadata: &Metadata, is_hidden: bool) -> bool {
+        let mut actual = Self::actual_flags(metadata);
+        if is_hidden {
+            actual |= HIDDEN;
+        }
+
+        actual & self.required_present == self.required_present
+            && actual & self.required_absent == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_required_and_negated_flags() {
+        let filter = FileFlagsFilter::from_string("hidden,!archive").unwrap();
+        assert_eq!(filter.required_present, HIDDEN);
+        assert_eq!(filter.required_absent, ARCHIVE);
+    }
+
+    #[test]
+    fn blank_segments_are_ignored() {
+        let filter = FileFlagsFilter::from_string(" hidden , , system ").unwrap();
+        assert_eq!(filter.required_present, HIDDEN | SYSTEM);
+        assert_eq!(filter.required_absent, 0);
+    }
+
+    #[test]
+    fn unknown_flag_name_is_rejected() {
+        assert!(FileFlagsFilter::from_string("bogus").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn applies_detects_symlinks_as_reparse_point() {
+        use crate::filter::test_support::TempDir;
+
+        let dir = TempDir::new("flags");
+        let target = dir.join("target");
+        let link = dir.join("link");
+        std::fs::write(&target, b"hi").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let filter = FileFlagsFilter::from_string("symlink").unwrap();
+        let link_metadata = std::fs::symlink_metadata(&link).unwrap();
+        let target_metadata = std::fs::symlink_metadata(&target).unwrap();
+
+        assert!(filter.applies(&link_metadata, false));
+        assert!(!filter.applies(&target_metadata, false));
+    }
+}
@@ -1,15 +1,25 @@
 pub use self::size::SizeFilter;
-pub use self::time::TimeFilter;
+pub use self::time::{TimeFilter, TimeType};
 
 #[cfg(unix)]
 pub use self::owner::OwnerFilter;
 
 pub use self::xattr::XAttrFilter;
 
+pub use self::flags::FileFlagsFilter;
+
+pub use self::stream::StreamFilter;
+
 mod size;
 mod time;
 
 #[cfg(unix)]
 mod owner;
 
+mod flags;
+mod stream;
 mod xattr;
+
+/// Test-only fixtures shared by this module's unit tests and `cli`'s.
+#[cfg(test)]
+pub(crate) mod test_support;
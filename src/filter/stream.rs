@@ -0,0 +1,127 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+/// Selects files carrying NTFS alternate data streams (ADS), e.g. the
+/// `Zone.Identifier` stream windows marks web downloads with.
+///
+/// The bare form (`--has-stream`) matches any file with at least one named
+/// data stream beyond the unnamed `::$DATA`. The `name` form
+/// (`--has-stream=NAME`) additionally requires a stream called `NAME`.
+///
+/// On non-Windows/non-NTFS targets this compiles to a no-op that never
+/// matches, so the flag stays universally accepted.
+#[derive(Debug, Clone)]
+pub struct StreamFilter {
+    // Only read by the `#[cfg(windows)]` `applies`; the non-Windows stub
+    // always returns `false` without consulting it.
+    #[cfg_attr(not(windows), allow(dead_code))]
+    name: Option<String>,
+}
+
+impl StreamFilter {
+    pub fn from_string(input: &str) -> Result<Self> {
+        Ok(StreamFilter {
+            name: if input.is_empty() {
+                None
+            } else {
+                Some(input.to_owned())
+            },
+        })
+    }
+
+    #[cfg(windows)]
+    pub fn applies(&self, path: &Path) -> bool {
+        windows::streams(path).any(|stream| match &self.name {
+            Some(name) => stream == *name,
+            None => true,
+        })
+    }
+
+    #[cfg(not(windows))]
+    pub fn applies(&self, _path: &Path) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bare_form_matches_any_stream() {
+        let filter = StreamFilter::from_string("").unwrap();
+        assert!(filter.name.is_none());
+    }
+
+    #[test]
+    fn named_form_requires_that_exact_stream() {
+        let filter = StreamFilter::from_string("Zone.Identifier").unwrap();
+        assert_eq!(filter.name.as_deref(), Some("Zone.Identifier"));
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn never_matches_on_non_windows_targets() {
+        let filter = StreamFilter::from_string("").unwrap();
+        assert!(!filter.applies(Path::new(".")));
+    }
+}
+
+#[cfg(windows)]
+mod windows {
+    use std::ffi::OsString;
+    use std::os::windows::ffi::{OsStrExt, OsStringExt};
+    use std::path::Path;
+
+    use windows_sys::Win32::Foundation::INVALID_HANDLE_VALUE;
+    use windows_sys::Win32::Storage::FileSystem::{
+        FindClose, FindFirstStreamW, FindNextStreamW, WIN32_FIND_STREAM_DATA,
+    };
+
+    /// Enumerate the named data streams of `path` via `FindFirstStreamW`/
+    /// `FindNextStreamW`, yielding each stream's name with the trailing
+    /// `:$DATA` suffix and leading `:` stripped, and skipping the unnamed
+    /// stream (`::$DATA`).
+    pub fn streams(path: &Path) -> impl Iterator<Item = String> {
+        let mut names = Vec::new();
+
+        unsafe {
+            let mut find_data: WIN32_FIND_STREAM_DATA = std::mem::zeroed();
+            let wide_path: Vec<u16> = path
+                .as_os_str()
+                .encode_wide()
+                .chain(std::iter::once(0))
+                .collect();
+
+            let handle = FindFirstStreamW(
+                wide_path.as_ptr(),
+                windows_sys::Win32::Storage::FileSystem::FindStreamInfoStandard,
+                &mut find_data as *mut _ as *mut _,
+                0,
+            );
+
+            if handle != INVALID_HANDLE_VALUE {
+                loop {
+                    let raw_name = &find_data.cStreamName;
+                    let len = raw_name.iter().position(|&c| c == 0).unwrap_or(raw_name.len());
+                    let name = OsString::from_wide(&raw_name[..len]).to_string_lossy().into_owned();
+
+                    if let Some(stripped) = name.strip_prefix(':').and_then(|s| s.strip_suffix(":$DATA")) {
+                        if !stripped.is_empty() {
+                            names.push(stripped.to_owned());
+                        }
+                    }
+
+                    if FindNextStreamW(handle, &mut find_data as *mut _ as *mut _) == 0 {
+                        break;
+                    }
+                }
+
+                FindClose(handle);
+            }
+        }
+
+        names.into_iter()
+    }
+}
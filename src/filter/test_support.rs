@@ -0,0 +1,36 @@
+//! A uniquely-named scratch directory for unit tests that need real files on
+//! disk, removed automatically when it goes out of scope.
+
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+pub(crate) struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Create the directory under `std::env::temp_dir()`, named
+    /// `fd-<label>-test-<thread id>` so that concurrently-run tests never
+    /// collide on the same path.
+    pub(crate) fn new(label: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!(
+            "fd-{}-test-{:?}",
+            label,
+            std::thread::current().id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        TempDir(dir)
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,60 @@
+use std::fs::Metadata;
+use std::time::SystemTime;
+
+/// Which of a file's timestamps a [`TimeFilter`] is evaluated against.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TimeType {
+    Modified,
+    Accessed,
+    Created,
+}
+
+impl TimeType {
+    /// Read the requested timestamp from metadata, returning `None` if the
+    /// platform/filesystem doesn't report it (e.g. no birth time) rather than
+    /// erroring, matching how fd tolerates missing metadata elsewhere.
+    fn read(self, metadata: &Metadata) -> Option<SystemTime> {
+        match self {
+            TimeType::Modified => metadata.modified().ok(),
+            TimeType::Accessed => metadata.accessed().ok(),
+            TimeType::Created => metadata.created().ok(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TimeFilter {
+    Before(SystemTime),
+    After(SystemTime),
+}
+
+impl TimeFilter {
+    fn from_str(ref_time: SystemTime, s: &str) -> Option<SystemTime> {
+        humantime::parse_duration(s)
+            .map(|duration| ref_time - duration)
+            .or_else(|_| humantime::parse_rfc3339_weak(s))
+            .ok()
+    }
+
+    pub fn before(ref_time: SystemTime, s: &str) -> Option<Self> {
+        Self::from_str(ref_time, s).map(TimeFilter::Before)
+    }
+
+    pub fn after(ref_time: SystemTime, s: &str) -> Option<Self> {
+        Self::from_str(ref_time, s).map(TimeFilter::After)
+    }
+
+    /// Test this filter against the given clock of `metadata`. Returns `true`
+    /// if the timestamp is unavailable, so that missing creation times don't
+    /// silently exclude every result.
+    pub fn applies_to(&self, time_type: TimeType, metadata: &Metadata) -> bool {
+        let Some(time) = time_type.read(metadata) else {
+            return true;
+        };
+
+        match self {
+            TimeFilter::Before(limit) => time <= *limit,
+            TimeFilter::After(limit) => time >= *limit,
+        }
+    }
+}
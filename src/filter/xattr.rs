@@ -1,19 +1,75 @@
 use std::ffi::OsString;
 
 use anyhow::{Ok, Result};
-// use regex::bytes::Regex;
+use regex::bytes::Regex;
 
 #[derive(Debug, Clone)]
 pub enum XAttrFilter {
     Has(OsString),
     Matches(OsString, Vec<u8>),
+    MatchesRegex(OsString, Regex),
 }
 
 impl XAttrFilter {
     pub fn from_string(input: &str) -> Result<Self> {
+        if let Some((name, pattern)) = input.split_once("=~") {
+            return Ok(Self::MatchesRegex(name.into(), Regex::new(pattern)?));
+        }
+
         match input.split_once("=") {
             Some(v) => Ok(Self::Matches(v.0.into(), v.1.into())),
             None => Ok(Self::Has(input.into())),
         }
     }
+
+    /// The attribute name this filter applies to.
+    pub fn name(&self) -> &OsString {
+        match self {
+            Self::Has(name) | Self::Matches(name, _) | Self::MatchesRegex(name, _) => name,
+        }
+    }
+
+    /// Evaluate this filter against the raw bytes of the attribute value, given
+    /// that the attribute is known to be present. Values aren't guaranteed to be
+    /// UTF-8, so matching happens on bytes rather than `str`.
+    pub fn matches(&self, value: &[u8]) -> bool {
+        match self {
+            Self::Has(_) => true,
+            Self::Matches(_, expected) => value == expected.as_slice(),
+            Self::MatchesRegex(_, re) => re.is_match(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bare_name_is_existence_check() {
+        let filter = XAttrFilter::from_string("user.comment").unwrap();
+        assert!(matches!(filter, XAttrFilter::Has(_)));
+        assert_eq!(filter.name().to_str(), Some("user.comment"));
+        assert!(filter.matches(b"anything"));
+    }
+
+    #[test]
+    fn name_equals_value_is_exact_match() {
+        let filter = XAttrFilter::from_string("user.comment=todo").unwrap();
+        assert!(filter.matches(b"todo"));
+        assert!(!filter.matches(b"TODO"));
+    }
+
+    #[test]
+    fn name_equals_tilde_pattern_is_regex_match() {
+        let filter = XAttrFilter::from_string("user.comment=~(?i)todo").unwrap();
+        assert!(matches!(filter, XAttrFilter::MatchesRegex(_, _)));
+        assert!(filter.matches(b"TODO: fix this"));
+        assert!(!filter.matches(b"done"));
+    }
+
+    #[test]
+    fn invalid_regex_is_rejected() {
+        assert!(XAttrFilter::from_string("user.comment=~(").is_err());
+    }
 }
@@ -0,0 +1,185 @@
+use std::path::Path;
+use std::time::SystemTime;
+
+use anyhow::Result;
+
+use crate::filesystem;
+
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
+
+/// The known '--format' placeholders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placeholder {
+    Path,
+    Basename,
+    ParentDir,
+    NoExt,
+    BasenameNoExt,
+    Size,
+    Mtime,
+    Owner,
+    Group,
+}
+
+/// The textual form of each [`Placeholder`], in the order they are tried when
+/// tokenizing a template.
+const PLACEHOLDERS: &[(&str, Placeholder)] = &[
+    ("{/.}", Placeholder::BasenameNoExt),
+    ("{//}", Placeholder::ParentDir),
+    ("{/}", Placeholder::Basename),
+    ("{.}", Placeholder::NoExt),
+    ("{size}", Placeholder::Size),
+    ("{mtime}", Placeholder::Mtime),
+    ("{owner}", Placeholder::Owner),
+    ("{group}", Placeholder::Group),
+    ("{}", Placeholder::Path),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+/// A parsed `--format` template, ready to be rendered against a search result.
+///
+/// Supports the same path placeholders as `--exec` ('{}', '{/}', '{//}', '{.}', '{/.}')
+/// plus metadata placeholders ('{size}', '{mtime}', and on unix '{owner}'/'{group}').
+///
+/// The template is tokenized once, up front, into literal text and placeholder
+/// tokens. Rendering then fills each placeholder from the original path/metadata
+/// rather than doing textual search-and-replace over an already-substituted
+/// string, so a path that happens to contain literal brace text (e.g. a file
+/// named `{size}`) is never reinterpreted as a placeholder.
+#[derive(Debug, Clone)]
+pub struct FormatTemplate {
+    tokens: Vec<Token>,
+}
+
+impl FormatTemplate {
+    pub fn parse(template: &str) -> Self {
+        let mut tokens = Vec::new();
+        let mut literal = String::new();
+        let mut rest = template;
+
+        while !rest.is_empty() {
+            if rest.starts_with('{') {
+                if let Some(&(text, placeholder)) =
+                    PLACEHOLDERS.iter().find(|(text, _)| rest.starts_with(text))
+                {
+                    if !literal.is_empty() {
+                        tokens.push(Token::Literal(std::mem::take(&mut literal)));
+                    }
+                    tokens.push(Token::Placeholder(placeholder));
+                    rest = &rest[text.len()..];
+                    continue;
+                }
+            }
+
+            let mut chars = rest.char_indices();
+            chars.next();
+            let next_boundary = chars.next().map(|(i, _)| i).unwrap_or(rest.len());
+            literal.push_str(&rest[..next_boundary]);
+            rest = &rest[next_boundary..];
+        }
+
+        if !literal.is_empty() {
+            tokens.push(Token::Literal(literal));
+        }
+
+        FormatTemplate { tokens }
+    }
+
+    /// Render the template for a single search result, fetching metadata lazily
+    /// (only if a metadata placeholder is actually present in the template).
+    pub fn generate(&self, path: &Path) -> Result<String> {
+        let mut out = String::new();
+        let needs_metadata = self.tokens.iter().any(|t| {
+            matches!(
+                t,
+                Token::Placeholder(
+                    Placeholder::Size | Placeholder::Mtime | Placeholder::Owner | Placeholder::Group
+                )
+            )
+        });
+        let metadata = if needs_metadata { path.metadata().ok() } else { None };
+
+        for token in &self.tokens {
+            match token {
+                Token::Literal(text) => out.push_str(text),
+                Token::Placeholder(Placeholder::Path) => out.push_str(&path.to_string_lossy()),
+                Token::Placeholder(Placeholder::Basename) => {
+                    if let Some(name) = path.file_name() {
+                        out.push_str(&name.to_string_lossy());
+                    }
+                }
+                Token::Placeholder(Placeholder::ParentDir) => {
+                    if let Some(parent) = path.parent() {
+                        out.push_str(&parent.to_string_lossy());
+                    }
+                }
+                Token::Placeholder(Placeholder::NoExt) => {
+                    if let Some(p) = filesystem::path_without_extension(path) {
+                        out.push_str(&p.to_string_lossy());
+                    }
+                }
+                Token::Placeholder(Placeholder::BasenameNoExt) => {
+                    if let Some(name) = filesystem::path_without_extension(path)
+                        .and_then(|p| p.file_name().map(|s| s.to_string_lossy().into_owned()))
+                    {
+                        out.push_str(&name);
+                    }
+                }
+                Token::Placeholder(Placeholder::Size) => {
+                    let size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
+                    out.push_str(&size.to_string());
+                }
+                Token::Placeholder(Placeholder::Mtime) => {
+                    if let Some(mtime) = metadata.as_ref().and_then(|m| m.modified().ok()) {
+                        out.push_str(&format_time(mtime));
+                    }
+                }
+                Token::Placeholder(Placeholder::Owner) => {
+                    #[cfg(unix)]
+                    if let Some(m) = &metadata {
+                        out.push_str(&m.uid().to_string());
+                    }
+                }
+                Token::Placeholder(Placeholder::Group) => {
+                    #[cfg(unix)]
+                    if let Some(m) = &metadata {
+                        out.push_str(&m.gid().to_string());
+                    }
+                }
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+fn format_time(time: SystemTime) -> String {
+    humantime::format_rfc3339_seconds(time).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal_brace_text_in_path_is_not_reinterpreted() {
+        let template = FormatTemplate::parse("{}");
+        let rendered = template.generate(Path::new("some/dir/{size}")).unwrap();
+        assert_eq!(rendered, "some/dir/{size}");
+    }
+
+    #[test]
+    fn literal_brace_text_around_a_real_placeholder() {
+        let template = FormatTemplate::parse("{/}-{.}");
+        let rendered = template
+            .generate(Path::new("some/{/}-dir/name.txt"))
+            .unwrap();
+        assert_eq!(rendered, "name.txt-some/{/}-dir/name");
+    }
+}